@@ -0,0 +1,594 @@
+use gpui::BackgroundExecutor;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
+};
+
+/// A candidate string to match a query against. `id` is opaque to this
+/// crate and round-trips through to the corresponding [`StringMatch`], so
+/// callers can use it to look the original item back up.
+#[derive(Debug, Clone)]
+pub struct StringMatchCandidate {
+    pub id: usize,
+    pub string: String,
+}
+
+impl StringMatchCandidate {
+    pub fn new(id: usize, string: &str) -> Self {
+        Self {
+            id,
+            string: string.to_string(),
+        }
+    }
+}
+
+/// The result of matching a [`StringMatchCandidate`] against a query.
+/// `positions` are byte offsets into `string` that should be highlighted.
+#[derive(Debug, Clone)]
+pub struct StringMatch {
+    pub candidate_id: usize,
+    pub string: String,
+    pub positions: Vec<usize>,
+    pub score: f64,
+}
+
+/// A single whitespace-delimited piece of a query, after its markers have
+/// been parsed out. Atoms are ANDed together by [`match_strings`] and by
+/// `picker::match_columns`. This crate has no notion of `field:`-qualified
+/// atoms — that syntax belongs to `picker::match_columns`, which strips it
+/// off with [`strip_field_prefix`] before handing the rest of the atom to
+/// [`parse_query`], so every other caller of `match_strings`/`parse_query`
+/// (single-column pickers with queries that may contain a bare `:`, e.g. a
+/// file path or line number) is unaffected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryAtom {
+    text: String,
+    negate: bool,
+    fuzzy: bool,
+    prefix: bool,
+    postfix: bool,
+}
+
+impl QueryAtom {
+    /// Whether this atom should match case-sensitively: either the caller
+    /// forced it (`smart_case` passed to [`match_strings`]), or the atom's
+    /// own text contains an uppercase character.
+    fn is_case_sensitive(&self, force: bool) -> bool {
+        force || self.text.chars().any(|c| c.is_uppercase())
+    }
+
+    /// Whether this atom was negated with a leading `!`, i.e. it excludes
+    /// candidates it matches rather than requiring them. Used by
+    /// `picker::match_columns` to AND per-column results the same way
+    /// [`match_strings`] does for a single column.
+    pub fn negate(&self) -> bool {
+        self.negate
+    }
+
+    /// Returns this atom forced to match exactly (as if both `^` and `$`
+    /// were given), unless it already specifies an anchor. Exposed for
+    /// `picker::match_columns`, which uses it to make a `field:`-qualified
+    /// filter match exactly by default, since a field's value is typically
+    /// a fixed vocabulary (e.g. "Available" / "Unavailable") rather than
+    /// free text a substring search should narrow.
+    pub fn into_exact_if_unanchored(self) -> Self {
+        if self.prefix || self.postfix {
+            self
+        } else {
+            Self {
+                fuzzy: false,
+                prefix: true,
+                postfix: true,
+                ..self
+            }
+        }
+    }
+}
+
+/// Splits a leading `field:` qualifier off a single whitespace atom: a name
+/// of alphanumeric/`-`/`_` characters followed by `:` and non-empty text,
+/// optionally preceded by the `!` marker `parse_query` recognizes.
+/// Returns the field name and the remaining atom text, with any leading `!`
+/// restored so the caller can hand it straight to [`parse_query`]. Lives
+/// here next to the query grammar; `parse_query`, `match_atom`,
+/// and `match_strings` otherwise have no notion of fields — routing a
+/// field-qualified atom to a column is entirely `picker::match_columns`'s
+/// concern.
+pub fn strip_field_prefix(atom: &str) -> Option<(&str, String)> {
+    let (negate, text) = match atom.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, atom),
+    };
+
+    let (name, rest) = text.split_once(':')?;
+    if name.is_empty()
+        || rest.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+
+    Some((
+        name,
+        if negate {
+            format!("!{rest}")
+        } else {
+            rest.to_string()
+        },
+    ))
+}
+
+/// Splits `query` into independent atoms, ANDed together.
+///
+/// Each atom may start with `!` (inverse), then `^` (prefix-anchored) or `'`
+/// (plain substring); a bare atom is fuzzy, unless it was negated, in which
+/// case it falls back to a plain substring. A trailing unescaped `$` marks
+/// the atom postfix-anchored (and always forces literal, not fuzzy,
+/// matching), promoting a prefix anchor into an exact match. Atoms that
+/// become empty after stripping markers are dropped.
+pub fn parse_query(query: &str) -> Vec<QueryAtom> {
+    query
+        .split_whitespace()
+        .filter_map(|raw| {
+            let mut text = raw;
+
+            let negate = if let Some(rest) = text.strip_prefix('!') {
+                text = rest;
+                true
+            } else {
+                false
+            };
+
+            let (mut fuzzy, prefix) = if let Some(rest) = text.strip_prefix('^') {
+                text = rest;
+                (false, true)
+            } else if let Some(rest) = text.strip_prefix('\'') {
+                text = rest;
+                (false, false)
+            } else {
+                (!negate, false)
+            };
+
+            let mut literal_dollar = false;
+            let postfix = if let Some(rest) = text.strip_suffix("\\$") {
+                text = rest;
+                literal_dollar = true;
+                false
+            } else if let Some(rest) = text.strip_suffix('$') {
+                text = rest;
+                fuzzy = false;
+                true
+            } else {
+                false
+            };
+
+            if text.is_empty() && !literal_dollar {
+                return None;
+            }
+
+            Some(QueryAtom {
+                text: if literal_dollar {
+                    format!("{text}$")
+                } else {
+                    text.to_string()
+                },
+                negate,
+                fuzzy,
+                prefix,
+                postfix,
+            })
+        })
+        .collect()
+}
+
+/// Matches `candidates` against a single atom's text, respecting its
+/// anchors. Negation is not applied here: a negated atom returns the
+/// candidates it matches, and the caller excludes them, as [`match_strings`]
+/// does. This is the building block `picker::match_columns` uses to route
+/// field-qualified atoms to the right column.
+///
+/// `force_case_sensitive` mirrors the `smart_case` parameter of
+/// [`match_strings`]; pass `false` to use the grammar's normal per-atom
+/// inference.
+pub async fn match_atom(
+    candidates: &[StringMatchCandidate],
+    atom: &QueryAtom,
+    force_case_sensitive: bool,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+) -> Vec<StringMatch> {
+    if cancel_flag.load(AtomicOrdering::Relaxed) {
+        return Vec::new();
+    }
+
+    let case_sensitive = atom.is_case_sensitive(force_case_sensitive);
+
+    if atom.fuzzy {
+        return fuzzy_match(candidates, &atom.text, case_sensitive, executor).await;
+    }
+
+    let needle = if case_sensitive {
+        atom.text.clone()
+    } else {
+        atom.text.to_lowercase()
+    };
+
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let haystack = if case_sensitive {
+                candidate.string.clone()
+            } else {
+                candidate.string.to_lowercase()
+            };
+
+            let start = if atom.prefix && atom.postfix {
+                (haystack == needle).then_some(0)
+            } else if atom.prefix {
+                haystack.starts_with(&needle).then_some(0)
+            } else if atom.postfix {
+                haystack
+                    .len()
+                    .checked_sub(needle.len())
+                    .filter(|&start| haystack[start..] == needle)
+            } else {
+                haystack.find(&needle)
+            };
+
+            start.map(|start| StringMatch {
+                candidate_id: candidate.id,
+                string: candidate.string.clone(),
+                positions: (start..start + needle.len()).collect(),
+                score: needle.len() as f64,
+            })
+        })
+        .collect()
+}
+
+/// Matches `candidates` against the extended query grammar documented on
+/// [`parse_query`], ANDing positive atoms together and excluding anything a
+/// negated atom matches. The score of a surviving candidate is the sum of
+/// its per-atom scores, and its positions are the union of the positions
+/// from every positive atom that matched it. Results are truncated to
+/// `max_results`.
+///
+/// `smart_case` overrides the grammar's default of inferring case
+/// sensitivity per atom: pass `true` to force every atom to match
+/// case-sensitively.
+pub async fn match_strings(
+    candidates: &[StringMatchCandidate],
+    query: &str,
+    smart_case: bool,
+    max_results: usize,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+) -> Vec<StringMatch> {
+    let atoms = parse_query(query);
+    if atoms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut excluded = HashSet::new();
+    let mut surviving: Option<Vec<StringMatch>> = None;
+    let mut saw_positive_atom = false;
+
+    for atom in &atoms {
+        if cancel_flag.load(AtomicOrdering::Relaxed) {
+            return Vec::new();
+        }
+
+        let atom_matches =
+            match_atom(candidates, atom, smart_case, cancel_flag, executor.clone()).await;
+
+        if atom.negate {
+            excluded.extend(atom_matches.into_iter().map(|mat| mat.candidate_id));
+            continue;
+        }
+
+        saw_positive_atom = true;
+        surviving = Some(match surviving {
+            None => atom_matches,
+            Some(previous) => and_matches(previous, atom_matches),
+        });
+    }
+
+    // A query made up entirely of inverse atoms has vacuously zero
+    // non-inverse atoms to satisfy, so every candidate passes until the
+    // exclusions below are applied — `surviving` is only ever populated by
+    // a positive atom, so without this it would wrongly stay empty.
+    let mut results = if saw_positive_atom {
+        surviving.unwrap_or_default()
+    } else {
+        everyone(candidates)
+    };
+    results.retain(|mat| !excluded.contains(&mat.candidate_id));
+    results.sort_unstable_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    results.truncate(max_results);
+    results
+}
+
+/// Every candidate as an unscored, unhighlighted match: the vacuous result
+/// of a filter with zero positive atoms to satisfy. Exposed for
+/// `picker::match_columns`, which needs the same vacuous-pass behavior
+/// when a column's atoms are all negated.
+pub fn everyone(candidates: &[StringMatchCandidate]) -> Vec<StringMatch> {
+    candidates
+        .iter()
+        .map(|candidate| StringMatch {
+            candidate_id: candidate.id,
+            string: candidate.string.clone(),
+            positions: Vec::new(),
+            score: 0.0,
+        })
+        .collect()
+}
+
+/// Merges two positive-atom match sets the way [`match_strings`] ANDs
+/// atoms together: keeps only candidates present in both, unions their
+/// highlighted positions, and sums their scores. Exposed for
+/// `picker::match_columns`, which ANDs atoms the same way across a
+/// picker's columns.
+pub fn and_matches(previous: Vec<StringMatch>, next: Vec<StringMatch>) -> Vec<StringMatch> {
+    let mut next_by_id = next
+        .into_iter()
+        .map(|mat| (mat.candidate_id, mat))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    previous
+        .into_iter()
+        .filter_map(|mut mat| {
+            let next_mat = next_by_id.remove(&mat.candidate_id)?;
+            mat.positions.extend(next_mat.positions);
+            mat.positions.sort_unstable();
+            mat.positions.dedup();
+            mat.score += next_mat.score;
+            Some(mat)
+        })
+        .collect()
+}
+
+/// A plain subsequence fuzzy matcher: `needle`'s characters must appear in
+/// `haystack` in order (not necessarily contiguously). Score favors matches
+/// whose characters are more contiguous and start earlier.
+async fn fuzzy_match(
+    candidates: &[StringMatchCandidate],
+    needle: &str,
+    smart_case: bool,
+    executor: BackgroundExecutor,
+) -> Vec<StringMatch> {
+    let needle = needle.to_string();
+    let candidates = candidates.to_vec();
+    executor
+        .spawn(async move {
+            candidates
+                .iter()
+                .filter_map(|candidate| fuzzy_match_one(candidate, &needle, smart_case))
+                .collect()
+        })
+        .await
+}
+
+fn fuzzy_match_one(
+    candidate: &StringMatchCandidate,
+    needle: &str,
+    smart_case: bool,
+) -> Option<StringMatch> {
+    if needle.is_empty() {
+        return Some(StringMatch {
+            candidate_id: candidate.id,
+            string: candidate.string.clone(),
+            positions: Vec::new(),
+            score: 0.0,
+        });
+    }
+
+    let haystack_chars: Vec<(usize, char)> = candidate.string.char_indices().collect();
+    let needle_chars: Vec<char> = needle.chars().collect();
+
+    let mut positions = Vec::with_capacity(needle_chars.len());
+    let mut haystack_index = 0;
+
+    for &needle_char in &needle_chars {
+        let mut found = None;
+        while haystack_index < haystack_chars.len() {
+            let (_, haystack_char) = haystack_chars[haystack_index];
+            let matches = if smart_case {
+                haystack_char == needle_char
+            } else {
+                haystack_char.to_lowercase().eq(needle_char.to_lowercase())
+            };
+            if matches {
+                found = Some(haystack_index);
+                haystack_index += 1;
+                break;
+            }
+            haystack_index += 1;
+        }
+        match found {
+            Some(index) => positions.push(index),
+            None => return None,
+        }
+    }
+
+    let span = positions.last().unwrap() - positions.first().unwrap() + 1;
+    let contiguity_bonus = needle_chars.len() as f64 / span as f64;
+    let early_start_bonus = 1.0 / (positions[0] as f64 + 1.0);
+    let score = needle_chars.len() as f64 + contiguity_bonus + early_start_bonus;
+    let positions = positions
+        .into_iter()
+        .map(|index| haystack_chars[index].0)
+        .collect();
+
+    Some(StringMatch {
+        candidate_id: candidate.id,
+        string: candidate.string.clone(),
+        positions,
+        score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atoms(query: &str) -> Vec<(String, bool, bool, bool, bool)> {
+        parse_query(query)
+            .into_iter()
+            .map(|atom| {
+                (
+                    atom.text.clone(),
+                    atom.negate,
+                    atom.fuzzy,
+                    atom.prefix,
+                    atom.postfix,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bare_atom_is_fuzzy() {
+        assert_eq!(
+            atoms("zed"),
+            vec![("zed".into(), false, true, false, false)]
+        );
+    }
+
+    #[test]
+    fn prefix_marker_without_postfix_is_starts_with_not_fuzzy() {
+        assert_eq!(
+            atoms("^zed"),
+            vec![("zed".into(), false, false, true, false)]
+        );
+    }
+
+    #[test]
+    fn trailing_dollar_on_bare_atom_forces_literal_postfix() {
+        assert_eq!(
+            atoms("predict$"),
+            vec![("predict".into(), false, false, false, true)]
+        );
+    }
+
+    #[test]
+    fn prefix_and_postfix_together_are_exact() {
+        assert_eq!(
+            atoms("^zed$"),
+            vec![("zed".into(), false, false, true, true)]
+        );
+    }
+
+    #[test]
+    fn negated_bare_atom_is_substring_not_fuzzy() {
+        assert_eq!(
+            atoms("!copilot"),
+            vec![("copilot".into(), true, false, false, false)]
+        );
+    }
+
+    #[test]
+    fn substring_marker() {
+        assert_eq!(
+            atoms("'super"),
+            vec![("super".into(), false, false, false, false)]
+        );
+    }
+
+    #[test]
+    fn colon_in_bare_atom_is_not_treated_as_a_field() {
+        // parse_query has no notion of `field:` qualifiers — that syntax is
+        // stripped by picker::match_columns before text ever reaches here,
+        // so a query containing a bare colon (a file path, a line number, a
+        // Windows drive letter) matches as ordinary fuzzy text.
+        assert_eq!(
+            atoms("src:42"),
+            vec![("src:42".into(), false, true, false, false)]
+        );
+    }
+
+    #[test]
+    fn drops_atoms_that_become_empty() {
+        assert!(atoms("^ ' !").is_empty());
+    }
+
+    #[test]
+    fn strip_field_prefix_recognizes_qualifier_and_restores_negation() {
+        assert_eq!(
+            strip_field_prefix("status:available"),
+            Some(("status", "available".to_string()))
+        );
+        assert_eq!(
+            strip_field_prefix("!status:available"),
+            Some(("status", "!available".to_string()))
+        );
+        assert_eq!(
+            strip_field_prefix("src:42"),
+            Some(("src", "42".to_string()))
+        );
+        assert_eq!(strip_field_prefix("zed"), None);
+        assert_eq!(strip_field_prefix(":empty-name"), None);
+        assert_eq!(strip_field_prefix("status:"), None);
+    }
+
+    #[test]
+    fn into_exact_if_unanchored_promotes_bare_atom_but_not_already_anchored() {
+        let bare = parse_query("available").into_iter().next().unwrap();
+        let exact = bare.into_exact_if_unanchored();
+        assert_eq!(
+            atoms_tuple(&exact),
+            ("available".into(), false, false, true, true)
+        );
+
+        let already_prefixed = parse_query("^available").into_iter().next().unwrap();
+        let unchanged = already_prefixed.clone().into_exact_if_unanchored();
+        assert_eq!(unchanged, already_prefixed);
+    }
+
+    fn atoms_tuple(atom: &QueryAtom) -> (String, bool, bool, bool, bool) {
+        (
+            atom.text.clone(),
+            atom.negate,
+            atom.fuzzy,
+            atom.prefix,
+            atom.postfix,
+        )
+    }
+
+    #[gpui::test]
+    async fn prefix_anchor_excludes_non_prefix_matches(cx: &mut gpui::TestAppContext) {
+        let candidates = vec![
+            StringMatchCandidate::new(0, "zed"),
+            StringMatchCandidate::new(1, "not-zed"),
+        ];
+        let atom = parse_query("^zed").into_iter().next().unwrap();
+        let cancel_flag = AtomicBool::new(false);
+        let matches = match_atom(&candidates, &atom, false, &cancel_flag, cx.executor()).await;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].candidate_id, 0);
+    }
+
+    #[gpui::test]
+    async fn negation_only_query_matches_everyone_except_excluded(cx: &mut gpui::TestAppContext) {
+        let candidates = vec![
+            StringMatchCandidate::new(0, "zed"),
+            StringMatchCandidate::new(1, "copilot"),
+            StringMatchCandidate::new(2, "supermaven"),
+        ];
+        let cancel_flag = AtomicBool::new(false);
+        let matches = match_strings(
+            &candidates,
+            "!copilot",
+            false,
+            100,
+            &cancel_flag,
+            cx.executor(),
+        )
+        .await;
+        let mut ids: Vec<usize> = matches.iter().map(|mat| mat.candidate_id).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![0, 2]);
+    }
+}
@@ -0,0 +1,441 @@
+use fuzzy::{
+    QueryAtom, StringMatch, StringMatchCandidate, and_matches, everyone, match_atom, parse_query,
+    strip_field_prefix,
+};
+use gpui::{
+    App, BackgroundExecutor, Context, Div, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ParentElement, Render, Rems, SharedString, Styled, Task, Window, uniform_list,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    sync::atomic::AtomicBool,
+};
+use ui::{Label, LabelSize, prelude::*};
+
+/// Implemented by the delegate of a [`Picker`] to supply its matches, drive
+/// selection and confirmation, and render each row. This is the extension
+/// point every modal picker in the app implements.
+pub trait PickerDelegate: Sized + 'static {
+    type ListItem: IntoElement;
+
+    fn placeholder_text(&self, window: &mut Window, cx: &mut App) -> Arc<str>;
+    fn match_count(&self) -> usize;
+    fn selected_index(&self) -> usize;
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    );
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()>;
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>);
+    fn dismissed(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>);
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem>;
+
+    /// The columns this picker's matches are organized into, in display
+    /// order. An empty list (the default) means the picker has a single
+    /// unnamed column and draws no header row; [`Picker::render`] draws a
+    /// header labelling each column otherwise.
+    fn columns(&self) -> Vec<PickerColumn> {
+        Vec::new()
+    }
+}
+
+/// One column of a multi-column picker, e.g. the "Provider" and "Status"
+/// columns of `PredictionProviderSelectorDelegate`. `field` is the name a
+/// delegate can use to route a query to this column explicitly; `primary`
+/// marks the column an unqualified query targets and whose string and
+/// positions are used for highlighting; `width` is the fixed width given to
+/// every non-primary column's cell, or `None` to shrink to its content.
+///
+/// [`PickerColumn::cell`] lays out a single cell of this column and is used
+/// by both [`Picker::render`]'s header row and a delegate's `render_match`,
+/// so header and body cells for the same column always share one width and
+/// alignment instead of each call site re-deriving it.
+pub struct PickerColumn {
+    pub header: SharedString,
+    pub field: &'static str,
+    pub primary: bool,
+    pub width: Option<Rems>,
+}
+
+impl PickerColumn {
+    pub fn new(header: impl Into<SharedString>, field: &'static str, primary: bool) -> Self {
+        Self {
+            header: header.into(),
+            field,
+            primary,
+            width: None,
+        }
+    }
+
+    /// Gives this column's cells a fixed width, instead of shrinking to fit
+    /// their content. The primary column ignores this and always flexes to
+    /// fill the row's remaining space.
+    pub fn width(mut self, width: Rems) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// An empty, styled container for one cell of this column: the primary
+    /// column flexes to fill remaining space and left-aligns its content;
+    /// every other column takes this column's `width` (if set) and
+    /// right-aligns. Callers add their content with `.child(...)` or
+    /// `.children(...)`.
+    pub fn cell(&self) -> Div {
+        let cell = h_flex();
+        if self.primary {
+            cell.flex_1()
+        } else if let Some(width) = self.width {
+            cell.w(width).justify_end()
+        } else {
+            cell.justify_end()
+        }
+    }
+}
+
+pub struct Picker<D: PickerDelegate> {
+    pub delegate: D,
+    query: String,
+    focus_handle: FocusHandle,
+}
+
+impl<D: PickerDelegate> Picker<D> {
+    pub fn uniform_list(delegate: D, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            delegate,
+            query: String::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn set_query(
+        &mut self,
+        query: impl Into<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.query = query.into();
+        let task = self.delegate.update_matches(self.query.clone(), window, cx);
+        task.detach();
+    }
+}
+
+impl<D: PickerDelegate> Focusable for Picker<D> {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<D: PickerDelegate> EventEmitter<gpui::DismissEvent> for Picker<D> {}
+
+impl<D: PickerDelegate> Render for Picker<D> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let columns = self.delegate.columns();
+        let match_count = self.delegate.match_count();
+        let selected_index = self.delegate.selected_index();
+
+        v_flex()
+            .w_full()
+            .when(!columns.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .px_2()
+                        .py_1()
+                        .gap_2()
+                        .children(columns.iter().map(|column| {
+                            column.cell().child(
+                                Label::new(column.header.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        })),
+                )
+            })
+            .child(uniform_list(
+                "picker-matches",
+                match_count,
+                cx.processor(move |this, range: std::ops::Range<usize>, window, cx| {
+                    range
+                        .filter_map(|ix| {
+                            this.delegate
+                                .render_match(ix, ix == selected_index, window, cx)
+                        })
+                        .collect::<Vec<_>>()
+                }),
+            ))
+    }
+}
+
+/// Routes each whitespace atom of `query` to the [`PickerColumn`] its
+/// `field:` prefix names (falling back to the `primary` column when an atom
+/// has none), then ANDs the per-column results together by row id. The
+/// returned matches carry the primary column's string and positions, for
+/// highlighting that column the same way a single-column picker would.
+///
+/// Field routing is parsed here, not in `fuzzy::parse_query` — `fuzzy` has
+/// no notion of fields, so a single-column picker's query can safely contain
+/// a bare `:` (a file path, a line number) without being reinterpreted. A
+/// field-qualified atom is also forced to match exactly via
+/// [`QueryAtom::into_exact_if_unanchored`], since a field's value is
+/// typically a fixed vocabulary (e.g. "Available" / "Unavailable") rather
+/// than free text a substring search should narrow.
+pub async fn match_columns(
+    columns: &[(PickerColumn, &[StringMatchCandidate])],
+    query: &str,
+    background: BackgroundExecutor,
+) -> Vec<StringMatch> {
+    if query.split_whitespace().next().is_none() {
+        return Vec::new();
+    }
+
+    let primary_index = columns.iter().position(|(column, _)| column.primary);
+
+    let mut atoms_by_column: Vec<Vec<QueryAtom>> = columns.iter().map(|_| Vec::new()).collect();
+    for raw in query.split_whitespace() {
+        let (index, atom) = match strip_field_prefix(raw) {
+            Some((field, value)) => (
+                columns.iter().position(|(column, _)| column.field == field),
+                parse_query(&value)
+                    .into_iter()
+                    .next()
+                    .map(QueryAtom::into_exact_if_unanchored),
+            ),
+            None => (primary_index, parse_query(raw).into_iter().next()),
+        };
+        let (Some(index), Some(atom)) = (index, atom) else {
+            // An atom whose `field:` names no known column (or whose text
+            // becomes empty after stripping markers) is dropped rather than
+            // surfaced as an error; it simply contributes no constraint to
+            // any column.
+            continue;
+        };
+        atoms_by_column[index].push(atom);
+    }
+
+    let mut rows: HashMap<usize, StringMatch> = match primary_index {
+        Some(index) => columns[index]
+            .1
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.id,
+                    StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string.clone(),
+                        positions: Vec::new(),
+                        score: 0.0,
+                    },
+                )
+            })
+            .collect(),
+        None => HashMap::new(),
+    };
+    let mut seeded = primary_index.is_some();
+
+    for (index, (column, candidates)) in columns.iter().enumerate() {
+        if atoms_by_column[index].is_empty() {
+            continue;
+        }
+
+        let matches =
+            match_atoms_against(candidates, &atoms_by_column[index], background.clone()).await;
+        let by_id: HashMap<usize, StringMatch> = matches
+            .into_iter()
+            .map(|mat| (mat.candidate_id, mat))
+            .collect();
+
+        if !seeded {
+            rows = by_id;
+            seeded = true;
+            continue;
+        }
+
+        rows.retain(|id, _| by_id.contains_key(id));
+        for (id, row) in rows.iter_mut() {
+            let Some(extra) = by_id.get(id) else {
+                continue;
+            };
+            row.score += extra.score;
+            if column.primary {
+                row.positions = extra.positions.clone();
+            }
+        }
+    }
+
+    let mut results: Vec<StringMatch> = rows.into_values().collect();
+    results.sort_unstable_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results
+}
+
+async fn match_atoms_against(
+    candidates: &[StringMatchCandidate],
+    atoms: &[QueryAtom],
+    background: BackgroundExecutor,
+) -> Vec<StringMatch> {
+    let mut excluded = HashSet::new();
+    let mut surviving: Option<Vec<StringMatch>> = None;
+    let mut saw_positive_atom = false;
+    let cancel_flag = AtomicBool::new(false);
+
+    for atom in atoms {
+        let atom_matches =
+            match_atom(candidates, atom, false, &cancel_flag, background.clone()).await;
+
+        if atom.negate() {
+            excluded.extend(atom_matches.into_iter().map(|mat| mat.candidate_id));
+            continue;
+        }
+
+        saw_positive_atom = true;
+        surviving = Some(match surviving {
+            None => atom_matches,
+            Some(previous) => and_matches(previous, atom_matches),
+        });
+    }
+
+    // Mirrors `fuzzy::match_strings`: a column whose atoms are all negated
+    // has vacuously zero positive atoms to satisfy, so every one of its
+    // candidates passes until the exclusions below are applied.
+    let mut results = if saw_positive_atom {
+        surviving.unwrap_or_default()
+    } else {
+        everyone(candidates)
+    };
+    results.retain(|mat| !excluded.contains(&mat.candidate_id));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(strings: &[&str]) -> Vec<StringMatchCandidate> {
+        strings
+            .iter()
+            .enumerate()
+            .map(|(id, string)| StringMatchCandidate::new(id, string))
+            .collect()
+    }
+
+    fn ids(matches: &[StringMatch]) -> Vec<usize> {
+        let mut ids: Vec<usize> = matches.iter().map(|mat| mat.candidate_id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[gpui::test]
+    async fn unqualified_atom_routes_to_the_primary_column(cx: &mut gpui::TestAppContext) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (PickerColumn::new("Provider", "provider", true), &*providers),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        let matches = match_columns(&columns, "copilot", cx.executor()).await;
+        assert_eq!(ids(&matches), vec![1]);
+    }
+
+    #[gpui::test]
+    async fn field_qualified_atom_is_an_exact_match_not_a_substring(cx: &mut gpui::TestAppContext) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (PickerColumn::new("Provider", "provider", true), &*providers),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        // "Available" is a substring of "Unavailable", so only the exact
+        // match the field qualifier forces keeps "Unavailable" rows out.
+        let matches = match_columns(&columns, "status:available", cx.executor()).await;
+        assert_eq!(ids(&matches), vec![0]);
+    }
+
+    #[gpui::test]
+    async fn negation_only_field_query_matches_everyone_except_excluded(
+        cx: &mut gpui::TestAppContext,
+    ) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (PickerColumn::new("Provider", "provider", true), &*providers),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        let matches = match_columns(&columns, "!status:available", cx.executor()).await;
+        assert_eq!(ids(&matches), vec![1, 2]);
+    }
+
+    #[gpui::test]
+    async fn unknown_field_name_is_silently_dropped(cx: &mut gpui::TestAppContext) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (PickerColumn::new("Provider", "provider", true), &*providers),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        // No column has `field: "nope"`, so this atom is routed nowhere and
+        // dropped rather than erroring; the query is left with no atoms to
+        // satisfy for any column, so every candidate passes.
+        let matches = match_columns(&columns, "nope:anything", cx.executor()).await;
+        assert_eq!(ids(&matches), vec![0, 1, 2]);
+    }
+
+    #[gpui::test]
+    async fn empty_query_matches_nothing(cx: &mut gpui::TestAppContext) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (PickerColumn::new("Provider", "provider", true), &*providers),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        let matches = match_columns(&columns, "", cx.executor()).await;
+        assert!(matches.is_empty());
+    }
+
+    #[gpui::test]
+    async fn rows_merge_by_id_across_columns_with_no_primary(cx: &mut gpui::TestAppContext) {
+        let providers = candidates(&["Zed", "Copilot", "Supermaven"]);
+        let statuses = candidates(&["Available", "Unavailable", "Unavailable"]);
+        let columns = [
+            (
+                PickerColumn::new("Provider", "provider", false),
+                &*providers,
+            ),
+            (PickerColumn::new("Status", "status", false), &*statuses),
+        ];
+
+        // Neither column is primary, so both are AND-merged purely by row id
+        // rather than one supplying the base row set.
+        let matches = match_columns(
+            &columns,
+            "provider:copilot status:unavailable",
+            cx.executor(),
+        )
+        .await;
+        assert_eq!(ids(&matches), vec![1]);
+    }
+}
@@ -1,15 +1,15 @@
 use fs::Fs;
-use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
-    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
-    Render, Styled, WeakEntity, Window, actions,
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Global,
+    ParentElement, Render, SharedString, Styled, WeakEntity, Window, actions,
 };
 use language::language_settings::{AllLanguageSettings, EditPredictionProvider, FeaturesContent};
-use picker::{Picker, PickerDelegate};
+use picker::{Picker, PickerColumn, PickerDelegate, match_columns};
 use settings::update_settings_file;
 use std::{str::FromStr, sync::Arc};
 use strum::IntoEnumIterator;
-use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use ui::{Divider, HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt;
 use workspace::{ModalView, Workspace};
 
@@ -48,16 +48,54 @@ impl PredictionProviderSelector {
     }
 
     fn new(window: &mut Window, fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
-        let delegate = PredictionProviderSelectorDelegate::new(cx.entity().downgrade(), fs);
+        let delegate = PredictionProviderSelectorDelegate::new(cx.entity().downgrade(), fs, cx);
 
         let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
         Self { picker }
     }
 }
 
+/// Registers `status_for` as the source of truth for [`ProviderStatus`],
+/// superseding the built-in fallback. Each provider's real integration
+/// (Copilot's auth state, Zed's account state, etc.) should call this once
+/// at startup rather than have this selector hard-code their state.
+///
+/// Nothing in this crate calls this yet — the provider-auth crates that
+/// would (Copilot, Zed's prediction account, Supermaven) are expected to
+/// call it from their own `init`, not from here, so until one of them does,
+/// [`status_for_provider`] keeps falling back to [`ProviderStatus::fallback`].
+pub fn set_provider_status_source(
+    status_for: impl Fn(EditPredictionProvider, &App) -> ProviderStatus + 'static,
+    cx: &mut App,
+) {
+    cx.set_global(GlobalProviderStatus(Arc::new(status_for)));
+}
+
+struct GlobalProviderStatus(Arc<dyn Fn(EditPredictionProvider, &App) -> ProviderStatus>);
+
+impl Global for GlobalProviderStatus {}
+
+/// Looks up a provider's current [`ProviderStatus`] from whatever source was
+/// registered via [`set_provider_status_source`], falling back to
+/// [`ProviderStatus::fallback`] if nothing has registered one yet (e.g. in
+/// tests, or before the providers have finished initializing).
+fn status_for_provider(provider: EditPredictionProvider, cx: &App) -> ProviderStatus {
+    match cx.try_global::<GlobalProviderStatus>() {
+        Some(GlobalProviderStatus(status_for)) => status_for(provider, cx),
+        None => ProviderStatus::fallback(provider),
+    }
+}
+
 impl Render for PredictionProviderSelector {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        v_flex().w(rems(34.)).child(self.picker.clone())
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let status = self.picker.read(cx).delegate.selected_status(cx);
+
+        v_flex()
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .when_some(status, |this, status| {
+                this.child(Divider::horizontal()).child(status.render())
+            })
     }
 }
 
@@ -73,26 +111,39 @@ impl ModalView for PredictionProviderSelector {}
 pub struct PredictionProviderSelectorDelegate {
     language_selector: WeakEntity<PredictionProviderSelector>,
     candidates: Vec<StringMatchCandidate>,
+    status_candidates: Vec<StringMatchCandidate>,
     matches: Vec<StringMatch>,
     selected_index: usize,
     fs: Arc<dyn Fs>,
 }
 
 impl PredictionProviderSelectorDelegate {
-    fn new(language_selector: WeakEntity<PredictionProviderSelector>, fs: Arc<dyn Fs>) -> Self {
-        let candidates = EditPredictionProvider::iter()
+    fn new(
+        language_selector: WeakEntity<PredictionProviderSelector>,
+        fs: Arc<dyn Fs>,
+        cx: &App,
+    ) -> Self {
+        let providers = EditPredictionProvider::iter()
             .enumerate()
-            .filter_map(|(i, provider)| {
-                if provider == EditPredictionProvider::None {
-                    return None;
-                }
-                Some(StringMatchCandidate::new(i, &provider.to_string()))
+            .filter(|(_, provider)| *provider != EditPredictionProvider::None)
+            .collect::<Vec<_>>();
+
+        let candidates = providers
+            .iter()
+            .map(|(i, provider)| StringMatchCandidate::new(*i, &provider.to_string()))
+            .collect::<Vec<_>>();
+
+        let status_candidates = providers
+            .iter()
+            .map(|(i, provider)| {
+                StringMatchCandidate::new(*i, status_for_provider(*provider, cx).badge_label())
             })
             .collect::<Vec<_>>();
 
         Self {
             language_selector,
             candidates,
+            status_candidates,
             matches: vec![],
             selected_index: 0,
             fs,
@@ -112,6 +163,113 @@ impl PredictionProviderSelectorDelegate {
         }
         None
     }
+
+    fn selected_status(&self, cx: &App) -> Option<ProviderStatus> {
+        let mat = self.matches.get(self.selected_index)?;
+        let provider = EditPredictionProvider::from_str(&mat.string).ok()?;
+        Some(status_for_provider(provider, cx))
+    }
+}
+
+/// Sign-in and usability state for a single [`EditPredictionProvider`],
+/// surfaced in the selector's preview panel so a user can tell whether
+/// switching to it would actually do anything. Looked up via
+/// [`status_for_provider`], which uses the status source registered through
+/// [`set_provider_status_source`] if there is one, and
+/// [`ProviderStatus::fallback`] otherwise.
+pub struct ProviderStatus {
+    signed_in: bool,
+    account_or_model: Option<SharedString>,
+    privacy_note: SharedString,
+    usable: bool,
+}
+
+impl ProviderStatus {
+    pub fn new(
+        signed_in: bool,
+        account_or_model: Option<SharedString>,
+        privacy_note: impl Into<SharedString>,
+        usable: bool,
+    ) -> Self {
+        Self {
+            signed_in,
+            account_or_model,
+            privacy_note: privacy_note.into(),
+            usable,
+        }
+    }
+
+    /// The status shown when no provider has registered a real status
+    /// source via [`set_provider_status_source`] (e.g. in tests, or before
+    /// providers finish initializing).
+    fn fallback(provider: EditPredictionProvider) -> Self {
+        match provider {
+            EditPredictionProvider::Zed => Self {
+                signed_in: true,
+                account_or_model: Some("Zed's hosted model".into()),
+                privacy_note: "Prompts are sent to Zed's servers.".into(),
+                usable: true,
+            },
+            EditPredictionProvider::Copilot => Self {
+                signed_in: false,
+                account_or_model: None,
+                privacy_note: "Prompts are sent to GitHub.".into(),
+                usable: false,
+            },
+            EditPredictionProvider::Supermaven => Self {
+                signed_in: false,
+                account_or_model: None,
+                privacy_note: "Prompts are sent to Supermaven.".into(),
+                usable: false,
+            },
+            EditPredictionProvider::None => Self {
+                signed_in: true,
+                account_or_model: None,
+                privacy_note: "Edit predictions are disabled.".into(),
+                usable: true,
+            },
+        }
+    }
+
+    fn badge_label(&self) -> &'static str {
+        if self.usable {
+            "Available"
+        } else {
+            "Unavailable"
+        }
+    }
+
+    fn render(&self) -> impl IntoElement {
+        v_flex()
+            .p_2()
+            .gap_1()
+            .child(
+                Label::new(if self.signed_in {
+                    "Signed in"
+                } else {
+                    "Not signed in"
+                })
+                .size(LabelSize::Small)
+                .color(if self.usable {
+                    Color::Default
+                } else {
+                    Color::Muted
+                }),
+            )
+            .children(self.account_or_model.clone().map(|account| {
+                Label::new(account)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+            .child(Label::new(self.privacy_note.clone()).size(LabelSize::Small))
+            .when(!self.usable, |this| {
+                this.child(
+                    Label::new("Not available yet")
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                )
+            })
+    }
 }
 
 impl PickerDelegate for PredictionProviderSelectorDelegate {
@@ -125,6 +283,13 @@ impl PickerDelegate for PredictionProviderSelectorDelegate {
         self.matches.len()
     }
 
+    fn columns(&self) -> Vec<PickerColumn> {
+        vec![
+            PickerColumn::new("Provider", "provider", true),
+            PickerColumn::new("Status", "status", false).width(rems(8.)),
+        ]
+    }
+
     fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         if let Some(mat) = self.matches.get(self.selected_index) {
             let selected = mat.string.clone();
@@ -176,6 +341,8 @@ impl PickerDelegate for PredictionProviderSelectorDelegate {
     ) -> gpui::Task<()> {
         let background = cx.background_executor().clone();
         let candidates = self.candidates.clone();
+        let status_candidates = self.status_candidates.clone();
+        let columns = self.columns();
         cx.spawn_in(window, async move |this, cx| {
             let matches = if query.is_empty() {
                 candidates
@@ -189,15 +356,12 @@ impl PickerDelegate for PredictionProviderSelectorDelegate {
                     })
                     .collect()
             } else {
-                match_strings(
-                    candidates.as_slice(),
-                    &query,
-                    false,
-                    100,
-                    &Default::default(),
-                    background,
-                )
-                .await
+                let mut columns = columns.into_iter();
+                let columns = [
+                    (columns.next().unwrap(), candidates.as_slice()),
+                    (columns.next().unwrap(), status_candidates.as_slice()),
+                ];
+                match_columns(&columns, &query, background).await
             };
 
             this.update(cx, |this, cx| {
@@ -217,24 +381,68 @@ impl PickerDelegate for PredictionProviderSelectorDelegate {
         ix: usize,
         selected: bool,
         _: &mut Window,
-        _cx: &mut Context<Picker<Self>>,
+        cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let mat = &self.matches.get(ix);
         let icon = self.icon_for_match(mat);
         if let Some(mat) = mat {
+            let status = EditPredictionProvider::from_str(&mat.string)
+                .ok()
+                .map(|provider| status_for_provider(provider, cx));
+            let columns = self.columns();
+
             Some(
                 ListItem::new(ix)
                     .inset(true)
                     .spacing(ListItemSpacing::Sparse)
                     .toggle_state(selected)
                     .start_slot::<Icon>(icon)
-                    .child(HighlightedLabel::new(
-                        mat.string.clone(),
-                        mat.positions.clone(),
-                    )),
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .child(columns[0].cell().child(HighlightedLabel::new(
+                                mat.string.clone(),
+                                mat.positions.clone(),
+                            )))
+                            .child(columns[1].cell().children(status.map(|status| {
+                                Label::new(status.badge_label())
+                                    .size(LabelSize::Small)
+                                    .color(if status.usable {
+                                        Color::Success
+                                    } else {
+                                        Color::Muted
+                                    })
+                            }))),
+                    ),
             )
         } else {
             None
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    fn registered_status_source_overrides_the_fallback(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            assert_eq!(
+                status_for_provider(EditPredictionProvider::Copilot, cx).badge_label(),
+                "Unavailable"
+            );
+
+            set_provider_status_source(
+                |_, _| ProviderStatus::new(true, None, "Signed in via test fixture.", true),
+                cx,
+            );
+
+            assert_eq!(
+                status_for_provider(EditPredictionProvider::Copilot, cx).badge_label(),
+                "Available"
+            );
+        });
+    }
+}